@@ -0,0 +1,34 @@
+// -*- mode: rust; -*-
+//
+// This file is part of tiny-ped-com.
+// Copyright (c) 2018 Alex Ledger
+// See LICENSE for licensing information.
+//
+// Authors:
+// - Alex Ledger <alex@alexledger.net>
+
+use std::error;
+use std::fmt;
+
+/// Errors returned when decoding the byte encodings of this crate's public types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The input was not exactly 32 bytes long.
+    InvalidLength,
+    /// The input did not decode to a valid Ristretto point.
+    InvalidPoint,
+    /// The input did not decode to a canonical scalar.
+    InvalidScalar,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::InvalidLength => write!(f, "expected a 32-byte encoding"),
+            Error::InvalidPoint => write!(f, "invalid compressed Ristretto point"),
+            Error::InvalidScalar => write!(f, "invalid or non-canonical scalar encoding"),
+        }
+    }
+}
+
+impl error::Error for Error {}
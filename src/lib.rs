@@ -30,26 +30,186 @@
 
 extern crate curve25519_dalek;
 extern crate rand;
+extern crate sha2;
+
+use std::ops::{Add, Mul, Sub};
 
 use rand::Rng;
-use curve25519_dalek::{constants, ristretto::{multiscalar_mul, RistrettoPoint}, scalar::Scalar};
+use curve25519_dalek::{constants, ristretto::{multiscalar_mul, CompressedRistretto, RistrettoPoint}, scalar::Scalar};
+use sha2::{Digest, Sha512};
+
+mod error;
+pub use error::Error;
+
+mod transcript;
+mod proofs;
+pub use proofs::{EqualityProof, OpeningProof};
+
+mod vector;
+pub use vector::{CommitterVec, VectorCommitment, VectorCommitmentKey, VectorCommitmentOpening};
+
+mod membership;
+pub use membership::MembershipProof;
+
+mod vss;
+pub use vss::{deal, recover, CoeffCommitment, Share};
 
 /// The Commitment created by the Committer. Sent to the Verifier so that the committer is bound
 /// to some value.
+#[derive(Clone, Copy)]
 pub struct Commitment(RistrettoPoint);
 
 /// The opening to the commitment. Sent by the committer to the verifier in the
 /// third round of communcation to prove that the commitment was for the associated value.
+#[derive(Clone, Copy)]
 pub struct CommitmentOpening(Scalar);
 
 /// The Verifier's public key. Sent to the Committer is the first round of communication.
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 pub struct VerifierPublicKey(RistrettoPoint);
 
 /// The value that the Committer is comitting to. Must be a valid scalar
 /// in the Ristretto field.
+#[derive(Clone, Copy)]
 pub struct CommitmentValue(Scalar);
 
+// Pedersen commitments are additively homomorphic: Commit(m1, r1) + Commit(m2, r2) is a
+// commitment to (m1 + m2, r1 + r2) under the same key. These impls let callers combine
+// commitments (e.g. summing confidential amounts) before opening the sum.
+
+impl Add for Commitment {
+    type Output = Commitment;
+    fn add(self, rhs: Commitment) -> Commitment {
+        Commitment(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Commitment {
+    type Output = Commitment;
+    fn sub(self, rhs: Commitment) -> Commitment {
+        Commitment(self.0 - rhs.0)
+    }
+}
+
+impl Mul<Scalar> for Commitment {
+    type Output = Commitment;
+    fn mul(self, rhs: Scalar) -> Commitment {
+        Commitment(self.0 * rhs)
+    }
+}
+
+impl Add for CommitmentOpening {
+    type Output = CommitmentOpening;
+    fn add(self, rhs: CommitmentOpening) -> CommitmentOpening {
+        CommitmentOpening(self.0 + rhs.0)
+    }
+}
+
+impl Sub for CommitmentOpening {
+    type Output = CommitmentOpening;
+    fn sub(self, rhs: CommitmentOpening) -> CommitmentOpening {
+        CommitmentOpening(self.0 - rhs.0)
+    }
+}
+
+impl Mul<Scalar> for CommitmentOpening {
+    type Output = CommitmentOpening;
+    fn mul(self, rhs: Scalar) -> CommitmentOpening {
+        CommitmentOpening(self.0 * rhs)
+    }
+}
+
+impl Add for CommitmentValue {
+    type Output = CommitmentValue;
+    fn add(self, rhs: CommitmentValue) -> CommitmentValue {
+        CommitmentValue(self.0 + rhs.0)
+    }
+}
+
+impl Sub for CommitmentValue {
+    type Output = CommitmentValue;
+    fn sub(self, rhs: CommitmentValue) -> CommitmentValue {
+        CommitmentValue(self.0 - rhs.0)
+    }
+}
+
+/// Decodes a 32-byte slice into a `CompressedRistretto`, rejecting anything but an exact
+/// 32-byte input. Decompression (and thus canonicity) is left to the caller.
+fn compressed_ristretto_from_slice(bytes: &[u8]) -> Result<CompressedRistretto, Error> {
+    if bytes.len() != 32 {
+        return Err(Error::InvalidLength);
+    }
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(bytes);
+    Ok(CompressedRistretto(buf))
+}
+
+/// Decodes a 32-byte slice into a canonical `Scalar`.
+fn scalar_from_slice(bytes: &[u8]) -> Result<Scalar, Error> {
+    if bytes.len() != 32 {
+        return Err(Error::InvalidLength);
+    }
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(bytes);
+    Scalar::from_canonical_bytes(buf).ok_or(Error::InvalidScalar)
+}
+
+impl Commitment {
+    /// Serializes the commitment to its canonical 32-byte compressed Ristretto encoding,
+    /// so it can be sent to the verifier or stored.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.compress().to_bytes()
+    }
+
+    /// Deserializes a commitment from its 32-byte compressed Ristretto encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        compressed_ristretto_from_slice(bytes)?
+            .decompress()
+            .map(Commitment)
+            .ok_or(Error::InvalidPoint)
+    }
+}
+
+impl CommitmentOpening {
+    /// Serializes the opening to its canonical 32-byte scalar encoding.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    /// Deserializes an opening from its 32-byte scalar encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        scalar_from_slice(bytes).map(CommitmentOpening)
+    }
+}
+
+impl CommitmentValue {
+    /// Serializes the value to its canonical 32-byte scalar encoding.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    /// Deserializes a value from its 32-byte scalar encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        scalar_from_slice(bytes).map(CommitmentValue)
+    }
+}
+
+impl VerifierPublicKey {
+    /// Serializes the public key to its canonical 32-byte compressed Ristretto encoding,
+    /// so it can be sent to the committer.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.compress().to_bytes()
+    }
+
+    /// Deserializes a public key from its 32-byte compressed Ristretto encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        compressed_ristretto_from_slice(bytes)?
+            .decompress()
+            .map(VerifierPublicKey)
+            .ok_or(Error::InvalidPoint)
+    }
+}
+
 /// Committer is the party who is commiting to a value.
 pub struct Committer;
 
@@ -68,8 +228,29 @@ impl CommitmentValue {
     }
 }
 
+const NUMS_GENERATOR_LABEL: &[u8] = b"tiny-ped-com-nums-generator";
+
+impl VerifierPublicKey {
+    /// Derives `H` as a nothing-up-my-sleeve generator: the image of a fixed
+    /// domain-separated hash under `RistrettoPoint::from_uniform_bytes`, so that *no one* —
+    /// not even the party calling this function — knows `log_G(H)`. Contrast with
+    /// `CommitVerifier::init`, where `H = a*G` for a secret `a` that the verifier must
+    /// discard for the scheme to stay binding.
+    pub fn from_nums() -> Self {
+        let mut hasher = Sha512::new();
+        hasher.input(NUMS_GENERATOR_LABEL);
+        let digest = hasher.result();
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(&digest);
+        VerifierPublicKey(RistrettoPoint::from_uniform_bytes(&bytes))
+    }
+}
+
 impl CommitVerifier {
-    /// Initialize the Verifier with a random number generator.
+    /// Initialize the Verifier with a random number generator. `H = a*G` for a secret
+    /// trapdoor `a` that is discarded after this call; binding relies on no one ever
+    /// recovering `a`. Applications that need binding without that trust assumption should
+    /// use `init_nums` instead.
     pub fn init<T: Rng>(mut rng: &mut T) -> (VerifierPublicKey, Self) {
         let a = Scalar::random(&mut rng);
         let G = &constants::RISTRETTO_BASEPOINT_POINT;
@@ -84,6 +265,21 @@ impl CommitVerifier {
         )
     }
 
+    /// Initializes the verifier with the nothing-up-my-sleeve generator from
+    /// `VerifierPublicKey::from_nums` instead of a trapdoor `H = a*G`. Unlike `init`, there
+    /// is no secret to discard: binding holds unconditionally because no party ever knows
+    /// `log_G(H)`.
+    pub fn init_nums() -> (VerifierPublicKey, Self) {
+        let pub_key = VerifierPublicKey::from_nums();
+        (
+            pub_key.clone(),
+            CommitVerifier {
+                pk: pub_key,
+                commitment: None,
+            },
+        )
+    }
+
     /// Gives the verifier the commitment received from the Committer.
     pub fn receive_commitment(&mut self, commitment: Commitment) {
         self.commitment = Some(commitment);
@@ -116,6 +312,18 @@ impl Committer {
         let C = multiscalar_mul(&[r, val_as_scalar], vec![G, &pub_key_point]);
         (Commitment(C), CommitmentOpening(r))
     }
+
+    /// Commits to `val` using a caller-supplied blinding factor instead of sampling a fresh
+    /// one. Useful when combining commitments homomorphically, where the caller needs to
+    /// control the blinding factors directly rather than let `commit` pick them.
+    pub fn commit_with_opening(val: &CommitmentValue, opening: &CommitmentOpening, pk: &VerifierPublicKey) -> Commitment {
+        let &CommitmentValue(val_as_scalar) = val;
+        let &CommitmentOpening(r) = opening;
+        let G = &constants::RISTRETTO_BASEPOINT_POINT;
+        let &VerifierPublicKey(pub_key_point) = pk;
+        let C = multiscalar_mul(&[r, val_as_scalar], vec![G, &pub_key_point]);
+        Commitment(C)
+    }
 }
 
 #[cfg(test)]
@@ -167,5 +375,78 @@ mod test {
         let did_verify = verifier.verify(&val, &commitment_opening);
         assert_eq!(did_verify, false);
     }
+
+    #[test]
+    fn homomorphic_sum_of_commitments_verifies() {
+        let mut rng = OsRng::new().unwrap();
+        let val1 = CommitmentValue::from_u64(3);
+        let val2 = CommitmentValue::from_u64(4);
+
+        let (verifier_pub_key, mut verifier) = CommitVerifier::init(&mut rng);
+        let (commitment1, opening1) = Committer::commit(&mut rng, &val1, &verifier_pub_key);
+        let (commitment2, opening2) = Committer::commit(&mut rng, &val2, &verifier_pub_key);
+
+        let summed_commitment = commitment1 + commitment2;
+        let summed_opening = opening1 + opening2;
+        let summed_val = val1 + val2;
+
+        verifier.receive_commitment(summed_commitment);
+        let did_verify = verifier.verify(&summed_val, &summed_opening);
+        assert_eq!(did_verify, true);
+    }
+
+    #[test]
+    fn commit_with_opening_matches_commit() {
+        let mut rng = OsRng::new().unwrap();
+        let val = CommitmentValue::from_u64(7);
+
+        let (verifier_pub_key, mut verifier) = CommitVerifier::init(&mut rng);
+        let (_commitment, opening) = Committer::commit(&mut rng, &val, &verifier_pub_key);
+
+        let recomputed_commitment = Committer::commit_with_opening(&val, &opening, &verifier_pub_key);
+        verifier.receive_commitment(recomputed_commitment);
+
+        let did_verify = verifier.verify(&val, &opening);
+        assert_eq!(did_verify, true);
+    }
+
+    #[test]
+    fn serialization_round_trips() {
+        let mut rng = OsRng::new().unwrap();
+        let val = CommitmentValue::from_u64(5);
+
+        let (verifier_pub_key, _verifier) = CommitVerifier::init(&mut rng);
+        let (commitment, opening) = Committer::commit(&mut rng, &val, &verifier_pub_key);
+
+        let decoded_pk = VerifierPublicKey::from_bytes(&verifier_pub_key.to_bytes()).unwrap();
+        let decoded_commitment = Commitment::from_bytes(&commitment.to_bytes()).unwrap();
+        let decoded_opening = CommitmentOpening::from_bytes(&opening.to_bytes()).unwrap();
+        let decoded_val = CommitmentValue::from_bytes(&val.to_bytes()).unwrap();
+
+        let (_, mut decoded_verifier) = CommitVerifier::init(&mut rng);
+        decoded_verifier.pk = decoded_pk;
+        decoded_verifier.receive_commitment(decoded_commitment);
+
+        let did_verify = decoded_verifier.verify(&decoded_val, &decoded_opening);
+        assert_eq!(did_verify, true);
+    }
+
+    #[test]
+    fn from_bytes_rejects_wrong_length() {
+        assert!(Commitment::from_bytes(&[0u8; 31]).is_err());
+        assert!(CommitmentOpening::from_bytes(&[0u8; 33]).is_err());
+    }
+
+    #[test]
+    fn nums_commitment_verifies() {
+        let mut rng = OsRng::new().unwrap();
+        let val = CommitmentValue::from_u64(6);
+
+        let (verifier_pub_key, mut verifier) = CommitVerifier::init_nums();
+        let (commitment, opening) = Committer::commit(&mut rng, &val, &verifier_pub_key);
+
+        verifier.receive_commitment(commitment);
+        assert_eq!(verifier.verify(&val, &opening), true);
+    }
 }
 
@@ -0,0 +1,307 @@
+// -*- mode: rust; -*-
+//
+// This file is part of tiny-ped-com.
+// Copyright (c) 2018 Alex Ledger
+// See LICENSE for licensing information.
+//
+// Authors:
+// - Alex Ledger <alex@alexledger.net>
+//!
+//! One-out-of-many membership proofs (Groth & Kohlweiss, "One-out-of-Many Proofs",
+//! EUROCRYPT 2015), showing knowledge of the opening of one commitment in a public set
+//! that commits to zero, without revealing which element or its blinding factor.
+//!
+//! The set is padded up to `N = 2^m` (this implementation fixes the paper's base at
+//! `n = 2`, i.e. a binary expansion of the secret index): each bit of the index gets its
+//! own 0/1 sigma proof, and all `N` branches are recombined into a single multiscalar
+//! check, so the proof is `O(m)` — logarithmic in the set size — rather than `O(N)`.
+
+use curve25519_dalek::{constants, ristretto::{multiscalar_mul, RistrettoPoint}, scalar::Scalar, traits::Identity};
+use rand::Rng;
+
+use crate::transcript::challenge_scalar;
+use crate::{Commitment, CommitmentOpening, CommitVerifier, Committer, VerifierPublicKey};
+
+const MEMBERSHIP_PROOF_LABEL: &[u8] = b"tiny-ped-com-membership-proof";
+
+fn bit_at(index: usize, j: usize) -> u64 {
+    ((index >> j) & 1) as u64
+}
+
+fn pow_scalar(x: Scalar, e: usize) -> Scalar {
+    let mut result = Scalar::one();
+    for _ in 0..e {
+        result *= x;
+    }
+    result
+}
+
+/// The first-message bit-commitment data for a single binary digit of the secret index.
+struct DigitCommitments {
+    b: RistrettoPoint,
+    a: RistrettoPoint,
+    c: RistrettoPoint,
+}
+
+/// A proof that the prover knows the opening of `set[index]` as a commitment to zero,
+/// without revealing `index`.
+pub struct MembershipProof {
+    digits: Vec<DigitCommitments>,
+    g_poly: Vec<RistrettoPoint>,
+    f: Vec<Scalar>,
+    z_a: Vec<Scalar>,
+    z_c: Vec<Scalar>,
+    z_d: Scalar,
+}
+
+impl Committer {
+    /// Proves that `set[index]` is a commitment to zero opened by `opening`, without
+    /// revealing `index` or `opening`. `set` is conceptually padded up to the next power of
+    /// two; entries beyond `set.len()` are treated as the identity commitment.
+    pub fn prove_membership<T: Rng>(
+        mut rng: &mut T,
+        set: &[Commitment],
+        index: usize,
+        opening: &CommitmentOpening,
+        pk: &VerifierPublicKey,
+    ) -> MembershipProof {
+        let n = set.len().next_power_of_two().max(2);
+        let m = (n as f64).log2().round() as usize;
+        let padded_n = 1usize << m;
+
+        let G = &constants::RISTRETTO_BASEPOINT_POINT;
+        let &VerifierPublicKey(H) = pk;
+
+        let mut a_vals = Vec::with_capacity(m);
+        let mut r_vals = Vec::with_capacity(m);
+        let mut s_vals = Vec::with_capacity(m);
+        let mut t_vals = Vec::with_capacity(m);
+        let mut digits = Vec::with_capacity(m);
+
+        for j in 0..m {
+            let bit = Scalar::from_u64(bit_at(index, j));
+            let a = Scalar::random(&mut rng);
+            let r = Scalar::random(&mut rng);
+            let s = Scalar::random(&mut rng);
+            let t = Scalar::random(&mut rng);
+
+            let b_commit = multiscalar_mul(&[r, bit], vec![G, &H]);
+            let a_commit = multiscalar_mul(&[s, a], vec![G, &H]);
+            let c_commit = multiscalar_mul(&[t, bit * a], vec![G, &H]);
+
+            digits.push(DigitCommitments { b: b_commit, a: a_commit, c: c_commit });
+            a_vals.push(a);
+            r_vals.push(r);
+            s_vals.push(s);
+            t_vals.push(t);
+        }
+
+        // p_k(x) = Π_j f_{j, k_j}(x) as a polynomial in x, where f_{j,1}(x) = bit_j*x + a_j
+        // and f_{j,0}(x) = x - f_{j,1}(x). Only the true index's polynomial has degree m (with
+        // leading coefficient 1); every other p_k has degree below m. The per-degree blinding
+        // commitments below (g_poly) hide that fact from the verifier.
+        let mut p_coeffs: Vec<Vec<Scalar>> = Vec::with_capacity(padded_n);
+        for k in 0..padded_n {
+            let mut coeffs = vec![Scalar::one()];
+            for j in 0..m {
+                let bit = bit_at(index, j);
+                let (lo, hi) = if bit_at(k, j) == 1 {
+                    (a_vals[j], Scalar::from_u64(bit))
+                } else {
+                    (-a_vals[j], Scalar::one() - Scalar::from_u64(bit))
+                };
+                let mut next = vec![Scalar::zero(); coeffs.len() + 1];
+                for (deg, coeff) in coeffs.iter().enumerate() {
+                    next[deg] += coeff * lo;
+                    next[deg + 1] += coeff * hi;
+                }
+                coeffs = next;
+            }
+            p_coeffs.push(coeffs);
+        }
+
+        let rho: Vec<Scalar> = (0..m).map(|_| Scalar::random(&mut rng)).collect();
+        let g_poly: Vec<RistrettoPoint> = (0..m)
+            .map(|d| {
+                let mut point = rho[d] * G;
+                for (k, coeffs) in p_coeffs.iter().enumerate() {
+                    if k < set.len() {
+                        if let Some(&coeff) = coeffs.get(d) {
+                            let &Commitment(ck) = &set[k];
+                            point += coeff * ck;
+                        }
+                    }
+                }
+                point
+            })
+            .collect();
+
+        let mut transcript_points: Vec<&RistrettoPoint> = vec![G, &H];
+        for d in &digits {
+            transcript_points.push(&d.b);
+            transcript_points.push(&d.a);
+            transcript_points.push(&d.c);
+        }
+        for g in &g_poly {
+            transcript_points.push(g);
+        }
+        let x = challenge_scalar(MEMBERSHIP_PROOF_LABEL, &transcript_points);
+
+        let mut f = Vec::with_capacity(m);
+        let mut z_a = Vec::with_capacity(m);
+        let mut z_c = Vec::with_capacity(m);
+        for j in 0..m {
+            let bit = Scalar::from_u64(bit_at(index, j));
+            let fj = bit * x + a_vals[j];
+            f.push(fj);
+            z_a.push(r_vals[j] * x + s_vals[j]);
+            z_c.push(r_vals[j] * (x - fj) + t_vals[j]);
+        }
+
+        let &CommitmentOpening(r_index) = opening;
+        let mut x_pow = Scalar::one();
+        let mut z_d = r_index * pow_scalar(x, m);
+        for &rho_d in &rho {
+            z_d -= rho_d * x_pow;
+            x_pow *= x;
+        }
+
+        MembershipProof { digits, g_poly, f, z_a, z_c, z_d }
+    }
+}
+
+impl CommitVerifier {
+    /// Verifies a `MembershipProof` that some element of `set` is a commitment to zero,
+    /// relative to this verifier's public key.
+    pub fn verify_membership(&self, set: &[Commitment], proof: &MembershipProof) -> bool {
+        let m = proof.digits.len();
+        if set.is_empty() || proof.f.len() != m || proof.g_poly.len() != m {
+            return false;
+        }
+
+        // m must be derived from the set the verifier actually has, not trusted from the
+        // proof: otherwise a prover could submit an undersized m and only bind the proof to
+        // a prefix of `set` while the verifier believes the whole set was covered.
+        let expected_m = set.len().next_power_of_two().max(2).trailing_zeros() as usize;
+        if m != expected_m {
+            return false;
+        }
+
+        let G = &constants::RISTRETTO_BASEPOINT_POINT;
+        let VerifierPublicKey(H) = self.pk;
+
+        let mut transcript_points: Vec<&RistrettoPoint> = vec![G, &H];
+        for d in &proof.digits {
+            transcript_points.push(&d.b);
+            transcript_points.push(&d.a);
+            transcript_points.push(&d.c);
+        }
+        for g in &proof.g_poly {
+            transcript_points.push(g);
+        }
+        let x = challenge_scalar(MEMBERSHIP_PROOF_LABEL, &transcript_points);
+
+        for (j, d) in proof.digits.iter().enumerate() {
+            let fj = proof.f[j];
+            if x * d.b + d.a != multiscalar_mul(&[proof.z_a[j], fj], vec![G, &H]) {
+                return false;
+            }
+            if (x - fj) * d.b + d.c != multiscalar_mul(&[proof.z_c[j], Scalar::zero()], vec![G, &H]) {
+                return false;
+            }
+        }
+
+        let padded_n = 1usize << m;
+        let mut combined = RistrettoPoint::identity();
+        for k in 0..padded_n.min(set.len()) {
+            let mut p_k = Scalar::one();
+            for j in 0..m {
+                let fj = proof.f[j];
+                p_k *= if bit_at(k, j) == 1 { fj } else { x - fj };
+            }
+            let &Commitment(ck) = &set[k];
+            combined += p_k * ck;
+        }
+
+        let mut x_pow = Scalar::one();
+        for g in &proof.g_poly {
+            combined -= x_pow * g;
+            x_pow *= x;
+        }
+
+        combined == proof.z_d * G
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CommitmentValue;
+    use rand::OsRng;
+
+    #[test]
+    fn membership_proof_verifies_for_true_index() {
+        let mut rng = OsRng::new().unwrap();
+        let (verifier_pub_key, verifier) = CommitVerifier::init(&mut rng);
+
+        let zero = CommitmentValue::from_u64(0);
+        let (c0, r0) = Committer::commit(&mut rng, &zero, &verifier_pub_key);
+        let (c1, _r1) = Committer::commit(&mut rng, &zero, &verifier_pub_key);
+        let (c2, _r2) = Committer::commit(&mut rng, &zero, &verifier_pub_key);
+        let (c3, _r3) = Committer::commit(&mut rng, &zero, &verifier_pub_key);
+        let set = vec![c0, c1, c2, c3];
+
+        let proof = Committer::prove_membership(&mut rng, &set, 0, &r0, &verifier_pub_key);
+        assert_eq!(verifier.verify_membership(&set, &proof), true);
+    }
+
+    #[test]
+    fn membership_proof_rejects_set_with_no_zero_commitment() {
+        let mut rng = OsRng::new().unwrap();
+        let (verifier_pub_key, verifier) = CommitVerifier::init(&mut rng);
+
+        // None of these commit to zero, so no index should ever verify.
+        let (c0, r0) = Committer::commit(&mut rng, &CommitmentValue::from_u64(1), &verifier_pub_key);
+        let (c1, _r1) = Committer::commit(&mut rng, &CommitmentValue::from_u64(2), &verifier_pub_key);
+        let (c2, _r2) = Committer::commit(&mut rng, &CommitmentValue::from_u64(3), &verifier_pub_key);
+        let (c3, _r3) = Committer::commit(&mut rng, &CommitmentValue::from_u64(4), &verifier_pub_key);
+        let set = vec![c0, c1, c2, c3];
+
+        let proof = Committer::prove_membership(&mut rng, &set, 0, &r0, &verifier_pub_key);
+        assert_eq!(verifier.verify_membership(&set, &proof), false);
+    }
+
+    #[test]
+    fn membership_proof_rejects_wrong_opening() {
+        let mut rng = OsRng::new().unwrap();
+        let (verifier_pub_key, verifier) = CommitVerifier::init(&mut rng);
+
+        let zero = CommitmentValue::from_u64(0);
+        let (c0, _r0) = Committer::commit(&mut rng, &zero, &verifier_pub_key);
+        let (c1, _r1) = Committer::commit(&mut rng, &zero, &verifier_pub_key);
+        let (c2, _r2) = Committer::commit(&mut rng, &zero, &verifier_pub_key);
+        let (c3, _r3) = Committer::commit(&mut rng, &zero, &verifier_pub_key);
+        let set = vec![c0, c1, c2, c3];
+
+        let wrong_opening = CommitmentOpening(Scalar::random(&mut rng));
+        let proof = Committer::prove_membership(&mut rng, &set, 0, &wrong_opening, &verifier_pub_key);
+        assert_eq!(verifier.verify_membership(&set, &proof), false);
+    }
+
+    #[test]
+    fn membership_proof_rejects_undersized_proof_for_set() {
+        let mut rng = OsRng::new().unwrap();
+        let (verifier_pub_key, verifier) = CommitVerifier::init(&mut rng);
+
+        let zero = CommitmentValue::from_u64(0);
+        let (c0, r0) = Committer::commit(&mut rng, &zero, &verifier_pub_key);
+        let (c1, _r1) = Committer::commit(&mut rng, &zero, &verifier_pub_key);
+        let small_set = vec![c0, c1];
+        let full_set = vec![c0, c1, c0, c1, c0, c1, c0, c1];
+
+        // A proof sized for a 2-element set must not verify against a larger set, even
+        // though the prefix it does cover is valid.
+        let proof = Committer::prove_membership(&mut rng, &small_set, 0, &r0, &verifier_pub_key);
+        assert_eq!(verifier.verify_membership(&full_set, &proof), false);
+    }
+}
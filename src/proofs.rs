@@ -0,0 +1,209 @@
+// -*- mode: rust; -*-
+//
+// This file is part of tiny-ped-com.
+// Copyright (c) 2018 Alex Ledger
+// See LICENSE for licensing information.
+//
+// Authors:
+// - Alex Ledger <alex@alexledger.net>
+//!
+//! Non-interactive zero-knowledge proofs about Pedersen commitments. Each proof is a
+//! Schnorr-style sigma protocol made non-interactive via Fiat-Shamir: the prover samples
+//! blinding scalars, commits to them, derives the challenge from `crate::transcript`, and
+//! responds. Because the challenge is bound to the exact transcript of public values, a
+//! proof cannot be replayed against a different commitment or a different key.
+
+use curve25519_dalek::{constants, ristretto::{multiscalar_mul, RistrettoPoint}, scalar::Scalar};
+use rand::Rng;
+
+use crate::transcript::challenge_scalar;
+use crate::{Commitment, CommitmentOpening, CommitmentValue, CommitVerifier, Committer, VerifierPublicKey};
+
+const OPENING_PROOF_LABEL: &[u8] = b"tiny-ped-com-opening-proof";
+const EQUALITY_PROOF_LABEL: &[u8] = b"tiny-ped-com-equality-proof";
+
+/// A non-interactive proof of knowledge of the `(m, r)` pair opening a `Commitment`,
+/// without revealing either value.
+pub struct OpeningProof {
+    t: RistrettoPoint,
+    z1: Scalar,
+    z2: Scalar,
+}
+
+impl Committer {
+    /// Proves knowledge of the `(val, opening)` pair opening `val*H + opening*G` relative to
+    /// `pk`, without revealing `val` or `opening`. The commitment being proven is derived
+    /// from `(val, opening, pk)` rather than taken as a parameter, so it can never
+    /// disagree with what the proof actually attests to.
+    pub fn prove_opening<T: Rng>(
+        mut rng: &mut T,
+        val: &CommitmentValue,
+        opening: &CommitmentOpening,
+        pk: &VerifierPublicKey,
+    ) -> OpeningProof {
+        let G = &constants::RISTRETTO_BASEPOINT_POINT;
+        let &VerifierPublicKey(H) = pk;
+        let &CommitmentValue(m) = val;
+        let &CommitmentOpening(r) = opening;
+        let &Commitment(C) = &Committer::commit_with_opening(val, opening, pk);
+
+        let s1 = Scalar::random(&mut rng);
+        let s2 = Scalar::random(&mut rng);
+        let t = multiscalar_mul(&[s1, s2], vec![G, &H]);
+
+        let c = challenge_scalar(OPENING_PROOF_LABEL, &[G, &H, &C, &t]);
+
+        OpeningProof {
+            t,
+            z1: s1 + c * r,
+            z2: s2 + c * m,
+        }
+    }
+}
+
+impl CommitVerifier {
+    /// Verifies an `OpeningProof` against the commitment currently held by this verifier.
+    pub fn verify_opening(&self, proof: &OpeningProof) -> bool {
+        let C = match self.commitment {
+            Some(Commitment(point)) => point,
+            None => panic!("No commitment received"),
+        };
+        let VerifierPublicKey(H) = self.pk;
+        let G = &constants::RISTRETTO_BASEPOINT_POINT;
+
+        let c = challenge_scalar(OPENING_PROOF_LABEL, &[G, &H, &C, &proof.t]);
+
+        let lhs = multiscalar_mul(&[proof.z1, proof.z2], vec![G, &H]);
+        let rhs = proof.t + c * C;
+        lhs == rhs
+    }
+}
+
+/// A non-interactive proof that two commitments `C1 = r1*G + m*H` and `C2 = r2*G + m*H`
+/// open to the same value `m`, without revealing `m`, `r1`, or `r2`.
+pub struct EqualityProof {
+    t1: RistrettoPoint,
+    t2: RistrettoPoint,
+    z_m: Scalar,
+    z1: Scalar,
+    z2: Scalar,
+}
+
+impl Committer {
+    /// Proves that `c1` (opened by `opening1`) and `c2` (opened by `opening2`) commit to the
+    /// same `val`, without revealing `val` or either blinding factor.
+    pub fn prove_equality<T: Rng>(
+        mut rng: &mut T,
+        c1: &Commitment,
+        opening1: &CommitmentOpening,
+        c2: &Commitment,
+        opening2: &CommitmentOpening,
+        val: &CommitmentValue,
+        pk: &VerifierPublicKey,
+    ) -> EqualityProof {
+        let G = &constants::RISTRETTO_BASEPOINT_POINT;
+        let &VerifierPublicKey(H) = pk;
+        let &CommitmentValue(m) = val;
+        let &CommitmentOpening(r1) = opening1;
+        let &CommitmentOpening(r2) = opening2;
+        let &Commitment(C1) = c1;
+        let &Commitment(C2) = c2;
+
+        let a = Scalar::random(&mut rng);
+        let b1 = Scalar::random(&mut rng);
+        let b2 = Scalar::random(&mut rng);
+        let t1 = multiscalar_mul(&[b1, a], vec![G, &H]);
+        let t2 = multiscalar_mul(&[b2, a], vec![G, &H]);
+
+        let c = challenge_scalar(EQUALITY_PROOF_LABEL, &[G, &H, &C1, &C2, &t1, &t2]);
+
+        EqualityProof {
+            t1,
+            t2,
+            z_m: a + c * m,
+            z1: b1 + c * r1,
+            z2: b2 + c * r2,
+        }
+    }
+}
+
+impl CommitVerifier {
+    /// Verifies an `EqualityProof` that `c1` and `c2` commit to the same value, relative to
+    /// this verifier's public key.
+    pub fn verify_equality(&self, c1: &Commitment, c2: &Commitment, proof: &EqualityProof) -> bool {
+        let VerifierPublicKey(H) = self.pk;
+        let G = &constants::RISTRETTO_BASEPOINT_POINT;
+        let &Commitment(C1) = c1;
+        let &Commitment(C2) = c2;
+
+        let c = challenge_scalar(EQUALITY_PROOF_LABEL, &[G, &H, &C1, &C2, &proof.t1, &proof.t2]);
+
+        let lhs1 = multiscalar_mul(&[proof.z1, proof.z_m], vec![G, &H]);
+        let rhs1 = proof.t1 + c * C1;
+        let lhs2 = multiscalar_mul(&[proof.z2, proof.z_m], vec![G, &H]);
+        let rhs2 = proof.t2 + c * C2;
+        lhs1 == rhs1 && lhs2 == rhs2
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{CommitVerifier, CommitmentValue, Committer};
+    use rand::OsRng;
+
+    #[test]
+    fn opening_proof_verifies() {
+        let mut rng = OsRng::new().unwrap();
+        let val = CommitmentValue::from_u64(9);
+
+        let (verifier_pub_key, mut verifier) = CommitVerifier::init(&mut rng);
+        let (commitment, opening) = Committer::commit(&mut rng, &val, &verifier_pub_key);
+        verifier.receive_commitment(commitment);
+
+        let proof = Committer::prove_opening(&mut rng, &val, &opening, &verifier_pub_key);
+        assert_eq!(verifier.verify_opening(&proof), true);
+    }
+
+    #[test]
+    fn opening_proof_rejects_wrong_commitment() {
+        let mut rng = OsRng::new().unwrap();
+        let val = CommitmentValue::from_u64(9);
+        let other_val = CommitmentValue::from_u64(10);
+
+        let (verifier_pub_key, mut verifier) = CommitVerifier::init(&mut rng);
+        let (_commitment, opening) = Committer::commit(&mut rng, &val, &verifier_pub_key);
+        let (other_commitment, _) = Committer::commit(&mut rng, &other_val, &verifier_pub_key);
+        verifier.receive_commitment(other_commitment);
+
+        let proof = Committer::prove_opening(&mut rng, &val, &opening, &verifier_pub_key);
+        assert_eq!(verifier.verify_opening(&proof), false);
+    }
+
+    #[test]
+    fn equality_proof_verifies_same_value() {
+        let mut rng = OsRng::new().unwrap();
+        let val = CommitmentValue::from_u64(11);
+
+        let (verifier_pub_key, verifier) = CommitVerifier::init(&mut rng);
+        let (c1, opening1) = Committer::commit(&mut rng, &val, &verifier_pub_key);
+        let (c2, opening2) = Committer::commit(&mut rng, &val, &verifier_pub_key);
+
+        let proof = Committer::prove_equality(&mut rng, &c1, &opening1, &c2, &opening2, &val, &verifier_pub_key);
+        assert_eq!(verifier.verify_equality(&c1, &c2, &proof), true);
+    }
+
+    #[test]
+    fn equality_proof_rejects_different_values() {
+        let mut rng = OsRng::new().unwrap();
+        let val1 = CommitmentValue::from_u64(11);
+        let val2 = CommitmentValue::from_u64(12);
+
+        let (verifier_pub_key, verifier) = CommitVerifier::init(&mut rng);
+        let (c1, opening1) = Committer::commit(&mut rng, &val1, &verifier_pub_key);
+        let (c2, opening2) = Committer::commit(&mut rng, &val2, &verifier_pub_key);
+
+        let proof = Committer::prove_equality(&mut rng, &c1, &opening1, &c2, &opening2, &val1, &verifier_pub_key);
+        assert_eq!(verifier.verify_equality(&c1, &c2, &proof), false);
+    }
+}
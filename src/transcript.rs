@@ -0,0 +1,29 @@
+// -*- mode: rust; -*-
+//
+// This file is part of tiny-ped-com.
+// Copyright (c) 2018 Alex Ledger
+// See LICENSE for licensing information.
+//
+// Authors:
+// - Alex Ledger <alex@alexledger.net>
+//!
+//! A minimal Fiat-Shamir transcript used to derive the non-interactive challenges for the
+//! sigma-protocol proofs in this crate. It hashes a domain-separation label followed by a
+//! fixed, ordered sequence of points with SHA-512 and reduces the digest into a `Scalar`.
+//! Every proof type feeds the same points in the same order on both sides, so the proof
+//! binds to that exact statement and is not replayable against a differently-labelled one.
+
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use sha2::{Digest, Sha512};
+
+/// Derives a Fiat-Shamir challenge `Scalar` from a domain-separation label and an ordered
+/// list of points.
+pub(crate) fn challenge_scalar(label: &[u8], points: &[&RistrettoPoint]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.input(label);
+    for point in points {
+        hasher.input(point.compress().as_bytes());
+    }
+    Scalar::from_hash(hasher)
+}
@@ -0,0 +1,149 @@
+// -*- mode: rust; -*-
+//
+// This file is part of tiny-ped-com.
+// Copyright (c) 2018 Alex Ledger
+// See LICENSE for licensing information.
+//
+// Authors:
+// - Alex Ledger <alex@alexledger.net>
+//!
+//! Vector Pedersen commitments: committing to several values at once under independent
+//! per-slot generators plus a single blinding base, `C = r*G + Σ m_i*H_i`. Each `H_i` is
+//! derived by hashing a labelled index into 64 bytes and mapping that uniformly onto the
+//! Ristretto group, so no party knows the discrete-log relationship between the `H_i` (or
+//! between any `H_i` and `G`), which is what keeps the scheme binding.
+
+use curve25519_dalek::{constants, ristretto::{multiscalar_mul, RistrettoPoint}, scalar::Scalar};
+use rand::Rng;
+use sha2::{Digest, Sha512};
+
+use crate::CommitmentValue;
+
+const GENERATOR_LABEL: &[u8] = b"tiny-ped-com-generator";
+
+/// A commitment to an `n`-element vector of values.
+pub struct VectorCommitment(RistrettoPoint);
+
+/// The opening to a `VectorCommitment`: the blinding factor `r`.
+pub struct VectorCommitmentOpening(Scalar);
+
+/// The public parameters for vector commitments of a fixed length: `n` independently
+/// derived generators `H_1..H_n` with no known discrete-log relationship to each other or
+/// to the blinding base `G`.
+pub struct VectorCommitmentKey {
+    generators: Vec<RistrettoPoint>,
+}
+
+impl VectorCommitmentKey {
+    /// Derives `n` nothing-up-my-sleeve generators for committing to vectors of length `n`.
+    pub fn new(n: usize) -> Self {
+        let generators = (0..n)
+            .map(|i| {
+                let mut hasher = Sha512::new();
+                hasher.input(GENERATOR_LABEL);
+                hasher.input(&(i as u64).to_le_bytes());
+                let digest = hasher.result();
+                let mut bytes = [0u8; 64];
+                bytes.copy_from_slice(&digest);
+                RistrettoPoint::from_uniform_bytes(&bytes)
+            })
+            .collect();
+        VectorCommitmentKey { generators }
+    }
+
+    /// The number of values this key can commit to at once.
+    pub fn len(&self) -> usize {
+        self.generators.len()
+    }
+
+    /// Whether this key has no generators, i.e. can only commit to the empty vector.
+    pub fn is_empty(&self) -> bool {
+        self.generators.is_empty()
+    }
+}
+
+/// `CommitterVec` is the party committing to a vector of values.
+pub struct CommitterVec;
+
+impl CommitterVec {
+    /// Commits to `vals` under `key`. `vals.len()` must equal `key.len()`.
+    pub fn commit<T: Rng>(
+        mut rng: &mut T,
+        vals: &[CommitmentValue],
+        key: &VectorCommitmentKey,
+    ) -> (VectorCommitment, VectorCommitmentOpening) {
+        assert_eq!(vals.len(), key.generators.len(), "value count must match the key length");
+
+        let r = Scalar::random(&mut rng);
+        let G = &constants::RISTRETTO_BASEPOINT_POINT;
+
+        let mut scalars = Vec::with_capacity(vals.len() + 1);
+        scalars.push(r);
+        scalars.extend(vals.iter().map(|&CommitmentValue(m)| m));
+
+        let mut points = Vec::with_capacity(vals.len() + 1);
+        points.push(G);
+        points.extend(key.generators.iter());
+
+        let C = multiscalar_mul(scalars, points);
+        (VectorCommitment(C), VectorCommitmentOpening(r))
+    }
+
+    /// Verifies that `commitment` opens to `vals` under `key` with blinding factor `opening`.
+    pub fn verify(
+        commitment: &VectorCommitment,
+        vals: &[CommitmentValue],
+        opening: &VectorCommitmentOpening,
+        key: &VectorCommitmentKey,
+    ) -> bool {
+        if vals.len() != key.generators.len() {
+            return false;
+        }
+
+        let &VectorCommitmentOpening(r) = opening;
+        let &VectorCommitment(C) = commitment;
+        let G = &constants::RISTRETTO_BASEPOINT_POINT;
+
+        let mut scalars = Vec::with_capacity(vals.len() + 1);
+        scalars.push(r);
+        scalars.extend(vals.iter().map(|&CommitmentValue(m)| m));
+
+        let mut points = Vec::with_capacity(vals.len() + 1);
+        points.push(G);
+        points.extend(key.generators.iter());
+
+        let C2 = multiscalar_mul(scalars, points);
+        C == C2
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::OsRng;
+
+    #[test]
+    fn vector_commitment_verifies() {
+        let mut rng = OsRng::new().unwrap();
+        let key = VectorCommitmentKey::new(3);
+        let vals = vec![
+            CommitmentValue::from_u64(1),
+            CommitmentValue::from_u64(2),
+            CommitmentValue::from_u64(3),
+        ];
+
+        let (commitment, opening) = CommitterVec::commit(&mut rng, &vals, &key);
+        assert_eq!(CommitterVec::verify(&commitment, &vals, &opening, &key), true);
+    }
+
+    #[test]
+    fn vector_commitment_rejects_tampered_value() {
+        let mut rng = OsRng::new().unwrap();
+        let key = VectorCommitmentKey::new(2);
+        let vals = vec![CommitmentValue::from_u64(1), CommitmentValue::from_u64(2)];
+        let tampered = vec![CommitmentValue::from_u64(1), CommitmentValue::from_u64(99)];
+
+        let (commitment, opening) = CommitterVec::commit(&mut rng, &vals, &key);
+        assert_eq!(CommitterVec::verify(&commitment, &tampered, &opening, &key), false);
+    }
+}
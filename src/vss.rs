@@ -0,0 +1,167 @@
+// -*- mode: rust; -*-
+//
+// This file is part of tiny-ped-com.
+// Copyright (c) 2018 Alex Ledger
+// See LICENSE for licensing information.
+//
+// Authors:
+// - Alex Ledger <alex@alexledger.net>
+//!
+//! Feldman/Pedersen verifiable secret sharing: splitting a secret scalar among `t`-of-`n`
+//! parties with publicly checkable shares, analogous to the distributed-key-generation
+//! flows in threshold crypto. This is built directly on the existing `G`/`H` generator
+//! pair, and `deal` accepts any secret scalar, such as a trapdoor that a caller generated
+//! itself. `CommitVerifier::init` discards its trapdoor `a` and never returns it, so that
+//! particular trapdoor cannot be dealt with this module.
+//!
+//! `H` here is always `VerifierPublicKey::from_nums()` (see `chunk0-6`) rather than a
+//! caller-supplied key: a dealt secret is typically the trapdoor itself, so the dealer
+//! cannot also be trusted to pick `H`, and nothing-up-my-sleeve is the one choice that
+//! needs no separate trust assumption.
+
+use curve25519_dalek::{constants, ristretto::{multiscalar_mul, RistrettoPoint}, scalar::Scalar, traits::Identity};
+use rand::Rng;
+
+use crate::VerifierPublicKey;
+
+/// A polynomial `f(x) = a_0 + a_1*x + ... + a_{t-1}*x^{t-1}` over the Ristretto scalar
+/// field, with `a_0` as the secret being shared.
+struct Polynomial {
+    coeffs: Vec<Scalar>,
+}
+
+impl Polynomial {
+    fn random<T: Rng>(mut rng: &mut T, constant_term: Scalar, degree: usize) -> Self {
+        let mut coeffs = Vec::with_capacity(degree + 1);
+        coeffs.push(constant_term);
+        for _ in 0..degree {
+            coeffs.push(Scalar::random(&mut rng));
+        }
+        Polynomial { coeffs }
+    }
+
+    fn eval(&self, x: Scalar) -> Scalar {
+        let mut result = Scalar::zero();
+        let mut x_pow = Scalar::one();
+        for &c in &self.coeffs {
+            result += c * x_pow;
+            x_pow *= x;
+        }
+        result
+    }
+}
+
+/// A Pedersen commitment to one coefficient of the dealer's polynomial, `A_j = a_j*G + b_j*H`.
+pub struct CoeffCommitment(RistrettoPoint);
+
+/// A single party's share of the dealt secret: its index and the corresponding evaluations
+/// of the secret and (hiding) blinding polynomials.
+pub struct Share {
+    index: u64,
+    s: Scalar,
+    s_prime: Scalar,
+}
+
+impl Share {
+    /// Verifies this share against the dealer's published coefficient commitments:
+    /// `s*G + s'*H == Σ_j index^j * A_j`.
+    pub fn verify(&self, commitments: &[CoeffCommitment]) -> bool {
+        let G = &constants::RISTRETTO_BASEPOINT_POINT;
+        let &VerifierPublicKey(H) = &VerifierPublicKey::from_nums();
+        let lhs = multiscalar_mul(&[self.s, self.s_prime], vec![G, &H]);
+
+        let x = Scalar::from_u64(self.index);
+        let mut rhs = RistrettoPoint::identity();
+        let mut x_pow = Scalar::one();
+        for CoeffCommitment(a) in commitments {
+            rhs += x_pow * a;
+            x_pow *= x;
+        }
+        lhs == rhs
+    }
+}
+
+/// Splits `secret` into `n` shares such that any `t` of them reconstruct it, publishing a
+/// Pedersen commitment to each coefficient of the underlying degree-`(t-1)` polynomial.
+pub fn deal<T: Rng>(
+    mut rng: &mut T,
+    secret: Scalar,
+    t: usize,
+    n: usize,
+) -> (Vec<Share>, Vec<CoeffCommitment>) {
+    assert!(t >= 1 && t <= n, "threshold must be between 1 and n");
+
+    let G = &constants::RISTRETTO_BASEPOINT_POINT;
+    let &VerifierPublicKey(H) = &VerifierPublicKey::from_nums();
+
+    let f = Polynomial::random(&mut rng, secret, t - 1);
+    let blinding_constant = Scalar::random(&mut rng);
+    let f_prime = Polynomial::random(&mut rng, blinding_constant, t - 1);
+
+    let commitments = f.coeffs.iter().zip(f_prime.coeffs.iter())
+        .map(|(&a, &b)| CoeffCommitment(multiscalar_mul(&[a, b], vec![G, &H])))
+        .collect();
+
+    let shares = (1..=n as u64)
+        .map(|i| {
+            let x = Scalar::from_u64(i);
+            Share {
+                index: i,
+                s: f.eval(x),
+                s_prime: f_prime.eval(x),
+            }
+        })
+        .collect();
+
+    (shares, commitments)
+}
+
+/// Reconstructs the secret from `t` or more valid shares via Lagrange interpolation at `x = 0`.
+pub fn recover(shares: &[Share]) -> Scalar {
+    let mut secret = Scalar::zero();
+    for (i, share_i) in shares.iter().enumerate() {
+        let xi = Scalar::from_u64(share_i.index);
+        let mut lagrange_coeff = Scalar::one();
+        for (j, share_j) in shares.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let xj = Scalar::from_u64(share_j.index);
+            lagrange_coeff *= xj * (xj - xi).invert();
+        }
+        secret += share_i.s * lagrange_coeff;
+    }
+    secret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::OsRng;
+
+    #[test]
+    fn shares_verify_and_recover_the_secret() {
+        let mut rng = OsRng::new().unwrap();
+        let secret = Scalar::random(&mut rng);
+
+        let (shares, commitments) = deal(&mut rng, secret, 3, 5);
+        for share in &shares {
+            assert_eq!(share.verify(&commitments), true);
+        }
+
+        let recovered = recover(&shares[0..3]);
+        assert_eq!(recovered, secret);
+    }
+
+    #[test]
+    fn any_threshold_subset_recovers_the_same_secret() {
+        let mut rng = OsRng::new().unwrap();
+        let secret = Scalar::random(&mut rng);
+
+        let (shares, _commitments) = deal(&mut rng, secret, 3, 5);
+        let recovered_a = recover(&shares[0..3]);
+        let recovered_b = recover(&shares[2..5]);
+        assert_eq!(recovered_a, secret);
+        assert_eq!(recovered_b, secret);
+    }
+}